@@ -0,0 +1,159 @@
+//! Build script.
+//!
+//! Its primary job — run on every build, feature flags aside — is to emit the `*.from.conv` /
+//! `*.to.conv` table sources into `OUT_DIR` from the checked-in `data/` tables, which `tables.rs`
+//! then `include_str!`s. Cargo runs exactly one build script, so this generation must happen here
+//! unconditionally; anything else is layered on top of it.
+//!
+//! When the `embed-dicts` (a.k.a. `codegen`) feature is enabled, it additionally performs the same
+//! merge-and-build pipeline that the crate otherwise runs at startup — but at compile time — and
+//! serializes each bundled variant's automaton + `target_words` into an embedded blob under
+//! `OUT_DIR`. At runtime `embedded::load` decodes a blob via `ZhConverter::from_bytes` instead of
+//! driving `CharwiseDoubleArrayAhoCorasickBuilder`. `get_builtin_converter` does not use these
+//! blobs; it builds its converters as before.
+//!
+//! The serialized layout mirrors [`ZhConverter::serialize_to_vec`]; keep the two in sync. Codegen is
+//! deterministic (sources are merged through a `BTreeMap`) so that repeated builds do not churn.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::path::PathBuf;
+
+use daachorse::{CharwiseDoubleArrayAhoCorasickBuilder, MatchKind};
+
+const SERIAL_MAGIC: &[u8; 4] = b"ZHCV";
+const SERIAL_VERSION: u8 = 1;
+const CODEC_NONE: u8 = 0;
+
+/// Table basenames, each backed by a `data/<name>.conv` source of `FROM<TAB>TO` lines.
+const TABLES: &[&str] = &["zh2Hant", "zh2Hans", "zh2TW", "zh2HK", "zh2CN"];
+
+/// `(variant tag, ordered list of table basenames merged base→region)`.
+const VARIANTS: &[(&str, &[&str])] = &[
+    ("zh-Hant", &["zh2Hant"]),
+    ("zh-Hans", &["zh2Hans"]),
+    ("zh-TW", &["zh2Hant", "zh2TW"]),
+    ("zh-HK", &["zh2Hant", "zh2HK"]),
+    ("zh-CN", &["zh2Hans", "zh2CN"]),
+];
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR set by cargo"));
+    generate_tables(&out_dir);
+    if env::var_os("CARGO_FEATURE_EMBED_DICTS").is_some() {
+        generate_embedded(&out_dir);
+    }
+}
+
+/// Split each `data/<name>.conv` source into the pipe-joined `<name>.from.conv` / `<name>.to.conv`
+/// pair under `OUT_DIR` that `tables.rs` includes. Always run, regardless of feature flags.
+fn generate_tables(out_dir: &Path) {
+    let data_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"))
+        .join("data");
+    for name in TABLES {
+        let src_path = data_dir.join(format!("{name}.conv"));
+        println!("cargo:rerun-if-changed=data/{name}.conv");
+        let src = fs::read_to_string(&src_path)
+            .unwrap_or_else(|e| panic!("reading {}: {e}", src_path.display()));
+        let mut froms = Vec::new();
+        let mut tos = Vec::new();
+        for line in src.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (from, to) = match line.split_once('\t') {
+                Some((from, to)) => (from, to),
+                None => (line, line),
+            };
+            froms.push(from);
+            tos.push(to);
+        }
+        fs::write(out_dir.join(format!("{name}.from.conv")), froms.join("|"))
+            .expect("write from.conv");
+        fs::write(out_dir.join(format!("{name}.to.conv")), tos.join("|")).expect("write to.conv");
+    }
+}
+
+/// Serialize each bundled variant's automaton into an embedded blob under `OUT_DIR/embedded`,
+/// reading the `*.conv` pair just produced by [`generate_tables`].
+fn generate_embedded(out_dir: &Path) {
+    let embed_dir = out_dir.join("embedded");
+    fs::create_dir_all(&embed_dir).expect("create embedded dir");
+
+    for (variant, tables) in VARIANTS {
+        // merge the ordered tables; a later (more specific) table overrides earlier ones per key
+        let mut mapping: BTreeMap<String, String> = BTreeMap::new();
+        for table in *tables {
+            let froms = fs::read_to_string(out_dir.join(format!("{table}.from.conv")))
+                .unwrap_or_else(|e| panic!("reading {table}.from.conv: {e}"));
+            let tos = fs::read_to_string(out_dir.join(format!("{table}.to.conv")))
+                .unwrap_or_else(|e| panic!("reading {table}.to.conv: {e}"));
+            for (from, to) in froms.trim().split('|').zip(tos.trim().split('|')) {
+                if !from.is_empty() {
+                    mapping.insert(from.to_owned(), to.to_owned());
+                }
+            }
+        }
+
+        let (source_words, target_words): (Vec<&String>, Vec<&String>) =
+            mapping.iter().map(|(f, t)| (f, t)).unzip();
+        let automaton = CharwiseDoubleArrayAhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build::<_, _, u32>(source_words.iter().map(|s| s.as_str()))
+            .expect("build automaton");
+
+        let blob = serialize(variant, &automaton.serialize(), &source_words, &target_words);
+        fs::write(embed_dir.join(format!("{variant}.bin")), blob).expect("write blob");
+    }
+}
+
+fn serialize(
+    variant: &str,
+    automaton: &[u8],
+    source_words: &[&String],
+    target_words: &[&String],
+) -> Vec<u8> {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&(automaton.len() as u64).to_le_bytes());
+    payload.extend_from_slice(automaton);
+    write_words(&mut payload, target_words);
+    // mirror ZhConverter::serialize_to_vec: source_words then max_pattern_len
+    write_words(&mut payload, source_words);
+    let max_pattern_len = source_words.iter().map(|w| w.len()).max().unwrap_or(0);
+    write_varint(&mut payload, max_pattern_len as u64);
+
+    let mut out = Vec::with_capacity(SERIAL_MAGIC.len() + 3 + variant.len() + payload.len());
+    out.extend_from_slice(SERIAL_MAGIC);
+    out.push(SERIAL_VERSION);
+    out.push(CODEC_NONE);
+    out.push(variant.len() as u8);
+    out.extend_from_slice(variant.as_bytes());
+    out.extend_from_slice(&payload);
+    out
+}
+
+fn write_words(out: &mut Vec<u8>, words: &[&String]) {
+    write_varint(out, words.len() as u64);
+    for word in words {
+        write_varint(out, word.len() as u64);
+        out.extend_from_slice(word.as_bytes());
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write;
+use std::io::{self, BufRead};
 use std::iter::IntoIterator;
 use std::str::FromStr;
 
 use daachorse::{CharwiseDoubleArrayAhoCorasick, CharwiseDoubleArrayAhoCorasickBuilder, MatchKind};
+use regex::Regex;
 
 use crate::tables::Table;
 use crate::{
@@ -17,13 +19,177 @@ use crate::{
 // Ref: https://github.com/wikimedia/mediawiki/blob/7bf779524ab1fd8e1d74f79ea4840564d48eea4d/includes/language/LanguageConverter.php#L76
 const NESTED_RULE_MAX_DEPTH: usize = 10;
 
+/// Magic bytes at the start of a serialized converter payload.
+const SERIAL_MAGIC: &[u8; 4] = b"ZHCV";
+/// Serialized payload format version.
+const SERIAL_VERSION: u8 = 1;
+/// Codec flag: payload stored uncompressed.
+const CODEC_NONE: u8 = 0;
+/// Codec flag: payload deflate-compressed.
+const CODEC_DEFLATE: u8 = 1;
+
+/// Append an unsigned LEB128 varint to `out`.
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    loop {
+        let mut byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// Read an unsigned LEB128 varint from the front of `input`, advancing it.
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+        shift += 7;
+    }
+}
+
+/// Read a little-endian `u64` from the front of `input`, advancing it.
+fn read_u64(input: &mut &[u8]) -> Option<u64> {
+    if input.len() < 8 {
+        return None;
+    }
+    let (head, rest) = input.split_at(8);
+    *input = rest;
+    Some(u64::from_le_bytes(head.try_into().unwrap()))
+}
+
+fn deflate_compress(bytes: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+    let mut encoder =
+        flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(bytes).expect("writing to Vec is infallible");
+    encoder.finish().expect("flushing to Vec is infallible")
+}
+
+fn deflate_decompress(bytes: &[u8]) -> Vec<u8> {
+    use std::io::Read;
+    let mut buf = Vec::new();
+    flate2::read::DeflateDecoder::new(bytes)
+        .read_to_end(&mut buf)
+        .expect("deflate payload is well-formed");
+    buf
+}
+
+/// A single substitution performed during a traced conversion, as returned by
+/// [`ZhConverter::convert_with_trace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    /// Byte range of the matched source word in the original text.
+    pub start: usize,
+    pub end: usize,
+    /// The original matched word (`text[start..end]`).
+    pub origin: String,
+    /// The substituted target word.
+    pub target: String,
+}
+
 /// A ZhConverter. See also [`ZhConverterBuilder`].
 pub struct ZhConverter {
     variant: Variant,
     automaton: CharwiseDoubleArrayAhoCorasick<u32>,
+    /// Source words, aligned by index with `target_words`. Retained so a merged automaton can be
+    /// rebuilt when page-level shadowing rules are applied.
+    source_words: Vec<String>,
     target_words: Vec<String>,
+    /// The maximum byte length among all source words, used to size the streaming tail window.
+    /// Zero when unknown (e.g. for converters created via [`new`](ZhConverter::new) or
+    /// [`with_target_variant`](ZhConverter::with_target_variant), which keep no source words).
+    max_pattern_len: usize,
+    /// Automaton over exclusion context phrases. A primary match falling inside one of the spans
+    /// this automaton reports is left unconverted. `None` when no exclusion is configured.
+    exclusion_automaton: Option<CharwiseDoubleArrayAhoCorasick<u32>>,
+    /// Regions matching these rules are copied verbatim, shielding URLs, code and Latin runs from
+    /// conversion. `None` when no protection is configured.
+    protection: Option<Protection>,
+}
+
+/// A set of rules describing regions of the input that must never be converted.
+#[derive(Debug, Default)]
+struct Protection {
+    /// User-supplied regexes whose matches are protected.
+    regexes: Vec<Regex>,
+    /// Whether to auto-protect contiguous ASCII/Latin runs (e.g. English words, filenames).
+    protect_latin: bool,
+}
+
+impl Protection {
+    /// Compute the union of protected byte ranges in `text`, merged and in ascending order.
+    fn spans(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut spans = Vec::new();
+        for re in &self.regexes {
+            spans.extend(re.find_iter(text).map(|m| (m.start(), m.end())));
+        }
+        if self.protect_latin {
+            let mut run_start = None;
+            for (i, ch) in text.char_indices() {
+                if ch.is_ascii_graphic() {
+                    run_start.get_or_insert(i);
+                } else if let Some(start) = run_start.take() {
+                    spans.push((start, i));
+                }
+            }
+            if let Some(start) = run_start {
+                spans.push((start, text.len()));
+            }
+        }
+        merge_spans(spans)
+    }
+}
+
+/// Duplicate an automaton by round-tripping through daachorse's own (de)serialization, since
+/// `CharwiseDoubleArrayAhoCorasick` is not `Clone`.
+fn clone_automaton(
+    automaton: &CharwiseDoubleArrayAhoCorasick<u32>,
+) -> CharwiseDoubleArrayAhoCorasick<u32> {
+    let bytes = automaton.serialize();
+    // SAFETY: `bytes` was just produced by `serialize` on a live, valid automaton.
+    unsafe { CharwiseDoubleArrayAhoCorasick::deserialize_unchecked(&bytes).0 }
 }
 
+/// Sort `spans` and merge overlapping or touching ones, yielding disjoint ranges in ascending order.
+fn merge_spans(mut spans: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    spans.sort_unstable();
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(spans.len());
+    for (s, e) in spans {
+        match merged.last_mut() {
+            Some(last) if s <= last.1 => last.1 = last.1.max(e),
+            _ => merged.push((s, e)),
+        }
+    }
+    merged
+}
+
+/// Built-in exclusion seed for the `ZhHans` direction, mirroring the exclusion set shipped with the
+/// `simplet2s` crate. Each entry is a `(segment, context_phrase)` pair meaning "do not apply the
+/// default conversion of `segment` when it occurs inside `context_phrase`".
+pub const ZH_HANS_EXCLUSION_SEED: &[(&str, &str)] = &[
+    ("覆", "答覆"),
+    ("覆", "批覆"),
+    ("覆", "回覆"),
+    ("甯", "甚甯"),
+    ("藉", "慰藉"),
+    ("藉", "狼藉"),
+    ("瞭", "瞭望"),
+    ("麽", "幺麽"),
+    ("幺", "幺麽"),
+    ("於", "樊於"),
+];
+
 impl ZhConverter {
     /// Create a new converter from a automaton and a mapping.
     ///
@@ -37,7 +203,11 @@ impl ZhConverter {
         ZhConverter {
             variant: Variant::Zh,
             automaton,
+            source_words: Vec::new(),
+            max_pattern_len: 0,
             target_words: target_words,
+            exclusion_automaton: None,
+            protection: None,
         }
     }
 
@@ -51,7 +221,11 @@ impl ZhConverter {
         ZhConverter {
             variant,
             automaton,
+            source_words: Vec::new(),
+            max_pattern_len: 0,
             target_words: target_words,
+            exclusion_automaton: None,
+            protection: None,
         }
     }
 
@@ -77,7 +251,235 @@ impl ZhConverter {
 
     /// Same as `convert`, except that it takes a `&mut String` as dest instead of returning a `String`.
     pub fn convert_to(&self, text: &str, output: &mut String) {
+        match self.protection.as_ref() {
+            // fast path: no protection rules, convert the whole text in one pass
+            None => self.convert_segment(text, output),
+            // split the input into alternating protected/convertible segments and convert only the
+            // latter, copying protected regions verbatim
+            Some(protection) => {
+                let mut last = 0;
+                for (s, e) in protection.spans(text) {
+                    if s > last {
+                        self.convert_segment(&text[last..s], output);
+                    }
+                    output.push_str(&text[s..e]);
+                    last = e;
+                }
+                self.convert_segment(&text[last..], output);
+            }
+        }
+    }
+
+    /// The maximum byte length among all source words.
+    ///
+    /// Useful for sizing the tail window of [`convert_stream`](Self::convert_stream). Zero for
+    /// converters created via [`new`](Self::new) or
+    /// [`with_target_variant`](Self::with_target_variant), where the source words are not kept.
+    pub fn max_pattern_len(&self) -> usize {
+        self.max_pattern_len
+    }
+
+    /// Convert a stream, reading from `src` and writing to `dst` without materializing the whole
+    /// input in memory.
+    ///
+    /// To honor `LeftmostLongest` semantics across chunk boundaries, a tail window of at least
+    /// [`max_pattern_len`](Self::max_pattern_len) bytes is buffered: output is flushed only up to
+    /// the point where no pending match could still extend into the next chunk, and the remainder
+    /// is carried forward. The output is byte-for-byte identical to the in-memory
+    /// [`convert`](Self::convert).
+    ///
+    /// The byte-for-byte guarantee holds only for converters without exclusion or protection rules,
+    /// whose spans are resolved over the whole input and cannot be honored incrementally. Streaming
+    /// such a converter returns an [`io::ErrorKind::Unsupported`] error rather than silently
+    /// diverging from [`convert`](Self::convert); use the in-memory path for those.
+    pub fn convert_stream<R: io::Read, W: io::Write>(
+        &self,
+        mut src: R,
+        mut dst: W,
+    ) -> io::Result<()> {
+        if self.exclusion_automaton.is_some() || self.protection.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "convert_stream does not support converters with exclusion or protection rules",
+            ));
+        }
+        // Without a known longest pattern we cannot choose a safe window, so fall back to reading
+        // the whole input and converting it in one pass.
+        if self.max_pattern_len == 0 {
+            let mut text = String::new();
+            src.read_to_string(&mut text)?;
+            let mut out = String::with_capacity(text.len());
+            self.convert_segment(&text, &mut out);
+            return dst.write_all(out.as_bytes());
+        }
+
+        let maxlen = self.max_pattern_len;
+        let mut buf: Vec<u8> = Vec::new();
+        let mut chunk = [0u8; 8192];
+        loop {
+            let n = src.read(&mut chunk)?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            // only the valid UTF-8 prefix is convertible; a split codepoint stays in `buf`
+            let valid = match std::str::from_utf8(&buf) {
+                Ok(_) => buf.len(),
+                Err(e) => e.valid_up_to(),
+            };
+            if valid <= maxlen {
+                continue;
+            }
+            let safe = Self::floor_char_boundary(&buf[..valid], valid - maxlen);
+            let text = std::str::from_utf8(&buf[..valid]).unwrap();
+            let mut out = String::new();
+            let consumed = self.convert_window(text, safe, &mut out);
+            dst.write_all(out.as_bytes())?;
+            buf.drain(..consumed);
+        }
+        // EOF: convert and flush whatever remains
+        let text = std::str::from_utf8(&buf)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "invalid UTF-8 at EOF"))?;
+        let mut out = String::with_capacity(text.len());
+        self.convert_segment(text, &mut out);
+        dst.write_all(out.as_bytes())
+    }
+
+    /// Convert the prefix of `text` that is safe to emit given the `safe` boundary, appending to
+    /// `out` and returning the number of bytes consumed. Matches starting at or after `safe` are
+    /// deferred since they might extend with future input.
+    fn convert_window(&self, text: &str, safe: usize, out: &mut String) -> usize {
+        let mut last = 0;
+        for m in self.automaton.leftmost_find_iter(text) {
+            let (s, e, ti) = (m.start(), m.end(), m.value());
+            if s >= safe {
+                break;
+            }
+            if s > last {
+                out.push_str(&text[last..s]);
+            }
+            out.push_str(&self.target_words[ti as usize]);
+            last = e;
+        }
+        if safe > last {
+            out.push_str(&text[last..safe]);
+            last = safe;
+        }
+        last
+    }
+
+    /// Round `index` down to the nearest UTF-8 char boundary within `bytes`.
+    fn floor_char_boundary(bytes: &[u8], mut index: usize) -> usize {
+        while index > 0 && (bytes[index] & 0xc0) == 0x80 {
+            index -= 1;
+        }
+        index
+    }
+
+    /// Convert `text`, leaving any region matched by one of `protected` untouched.
+    ///
+    /// This is the per-call counterpart to the builder-configured protection set (see
+    /// [`ZhConverterBuilder::protect`]). The input is scanned for protected matches first, then the
+    /// usual conversion runs only over the gaps between them, and the protected spans are copied
+    /// verbatim. It generalizes the internal `shadowed_source_words` skip logic and gives callers a
+    /// clean way to shield inline code, template names or ambiguous phrases without wrapping every
+    /// occurrence in `-{...}-` wikitext.
+    pub fn convert_with_protected(&self, text: &str, protected: &[Regex]) -> String {
+        let mut output = String::with_capacity(text.len());
+        let spans = merge_spans(
+            protected
+                .iter()
+                .flat_map(|re| re.find_iter(text).map(|m| (m.start(), m.end())))
+                .collect(),
+        );
+        let mut last = 0;
+        for (s, e) in spans {
+            if s > last {
+                self.convert_to(&text[last..s], &mut output);
+            }
+            output.push_str(&text[s..e]);
+            last = e;
+        }
+        self.convert_to(&text[last..], &mut output);
+        output
+    }
+
+    /// Convert `text`, also returning the list of substitutions that were applied.
+    ///
+    /// Each [`Replacement`] records the source byte range (in the original text), the original
+    /// matched word and the substituted target word. Identity rules — where the target equals the
+    /// matched source, e.g. protected phrases — are not reported as changes. The plain
+    /// [`convert`](Self::convert) hot path is left untouched; this is a parallel loop.
+    pub fn convert_with_trace(&self, text: &str) -> (String, Vec<Replacement>) {
+        let mut output = String::with_capacity(text.len());
+        let mut replacements = Vec::new();
+        match self.protection.as_ref() {
+            None => self.trace_segment(text, 0, &mut output, &mut replacements),
+            Some(protection) => {
+                let mut last = 0;
+                for (s, e) in protection.spans(text) {
+                    if s > last {
+                        self.trace_segment(&text[last..s], last, &mut output, &mut replacements);
+                    }
+                    output.push_str(&text[s..e]);
+                    last = e;
+                }
+                self.trace_segment(&text[last..], last, &mut output, &mut replacements);
+            }
+        }
+        (output, replacements)
+    }
+
+    /// Traced counterpart of [`convert_segment`](Self::convert_segment). `base` is the byte offset
+    /// of `text` within the original input, used to report absolute ranges.
+    fn trace_segment(
+        &self,
+        text: &str,
+        base: usize,
+        output: &mut String,
+        replacements: &mut Vec<Replacement>,
+    ) {
+        let exclusions = self.exclusion_spans(text);
+        let mut ei = 0;
+        let mut last = 0;
+        for (s, e, ti) in self
+            .automaton
+            .leftmost_find_iter(text)
+            .map(|m| (m.start(), m.end(), m.value()))
+        {
+            while ei < exclusions.len() && exclusions[ei].1 <= s {
+                ei += 1;
+            }
+            if ei < exclusions.len() && exclusions[ei].0 <= s && e <= exclusions[ei].1 {
+                continue;
+            }
+            if s > last {
+                output.push_str(&text[last..s]);
+            }
+            let target = &self.target_words[ti as usize];
+            // only report genuine changes, leaving identity rules out of the trace
+            if target != &text[s..e] {
+                replacements.push(Replacement {
+                    start: base + s,
+                    end: base + e,
+                    origin: text[s..e].to_owned(),
+                    target: target.clone(),
+                });
+            }
+            output.push_str(target);
+            last = e;
+        }
+        output.push_str(&text[last..]);
+    }
+
+    /// Convert a single convertible segment, applying the primary automaton and exclusion spans.
+    fn convert_segment(&self, text: &str, output: &mut String) {
         // Ref: https://github.dev/rust-lang/regex/blob/5197f21287344d2994f9cf06758a3ea30f5a26c3/src/re_trait.rs#L192
+        // Collect the exclusion spans (if any) in a single forward scan. They are already in
+        // leftmost-longest, non-decreasing order, so a cursor suffices to test containment while
+        // iterating the primary matches, keeping the whole pass linear.
+        let exclusions = self.exclusion_spans(text);
+        let mut ei = 0;
         let mut last = 0;
         // let mut cnt = HashMap::<usize, usize>::new();
         // leftmost-longest matching
@@ -86,6 +488,14 @@ impl ZhConverter {
             .leftmost_find_iter(text)
             .map(|m| (m.start(), m.end(), m.value()))
         {
+            // advance the exclusion cursor past spans that end before this match starts
+            while ei < exclusions.len() && exclusions[ei].1 <= s {
+                ei += 1;
+            }
+            // skip the substitution if the match falls inside an exclusion span
+            if ei < exclusions.len() && exclusions[ei].0 <= s && e <= exclusions[ei].1 {
+                continue;
+            }
             if s > last {
                 output.push_str(&text[last..s]);
             }
@@ -96,11 +506,40 @@ impl ZhConverter {
         output.push_str(&text[last..]);
     }
 
-    /// Convert a text, a long with a secondary conversion table (typically temporary).
+    /// Scan `text` with the exclusion automaton, returning the union of matched byte ranges in
+    /// ascending order. Overlapping spans are merged so that the longest protected region wins.
+    fn exclusion_spans(&self, text: &str) -> Vec<(usize, usize)> {
+        let automaton = match self.exclusion_automaton.as_ref() {
+            Some(automaton) => automaton,
+            None => return Vec::new(),
+        };
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        for m in automaton.leftmost_find_iter(text) {
+            let (s, e) = (m.start(), m.end());
+            match spans.last_mut() {
+                Some(last) if s <= last.1 => last.1 = last.1.max(e),
+                _ => spans.push((s, e)),
+            }
+        }
+        spans
+    }
+
+    /// Whether the base mapping can be reconstructed for a merged shadowing automaton.
     ///
-    /// The worst-case time complexity of the implementation is `O(n*m)` where `n` and `m` are the
-    /// length of the text and the maximum lengths of sources words in the secondary table
-    /// (i.e. brute-force).
+    /// Converters built via [`ZhConverterBuilder::build`] (and [`from_bytes`](Self::from_bytes))
+    /// retain their `source_words`; those produced by [`new`](Self::new) /
+    /// [`with_target_variant`](Self::with_target_variant) do not, and must fall back to the
+    /// dual-automaton path in [`convert_to_with`](Self::convert_to_with).
+    fn can_merge_shadowing(&self) -> bool {
+        self.source_words.len() == self.target_words.len() && !self.target_words.is_empty()
+    }
+
+    /// Convert a text, along with a secondary conversion table (typically temporary).
+    ///
+    /// This is the fallback used when the base `source_words` are unavailable and a merged automaton
+    /// cannot be rebuilt (see [`with_shadowing`](Self::with_shadowing)). The worst-case time
+    /// complexity is `O(n*m)` where `n` and `m` are the length of the text and the maximum length of
+    /// source words in the secondary table (i.e. brute-force).
     fn convert_to_with(
         &self,
         mut text: &str,
@@ -109,7 +548,6 @@ impl ZhConverter {
         shadowing_target_words: &[String],
         shadowed_source_words: &HashSet<String>,
     ) {
-        // let mut cnt = HashMap::<usize, usize>::new();
         while !text.is_empty() {
             // leftmost-longest matching
             let (s, e, target_word) = match (
@@ -152,12 +590,65 @@ impl ZhConverter {
             if s > 0 {
                 output.push_str(&text[..s]);
             }
-            // *cnt.entry(text[s..e].chars().count()).or_insert(0) += 1;
             output.push_str(target_word);
             text = &text[e..];
         }
     }
 
+    /// Build a new converter that layers page-level shadowing rules over this one's base table.
+    ///
+    /// Shadowing pairs override base entries with the same source key; shadowed (removed) source
+    /// words are dropped before the merged automaton is built. Because daachorse's `LeftmostLongest`
+    /// match kind resolves overlaps deterministically, feeding the union as a single automaton
+    /// yields correct shadowing semantics in one linear pass — no dual-iterator merge or degraded
+    /// single-char re-search is needed.
+    ///
+    /// Only callable when [`can_merge_shadowing`](Self::can_merge_shadowing) holds; otherwise the
+    /// base table would be silently lost.
+    fn with_shadowing(
+        &self,
+        shadowing_pairs: HashMap<String, String>,
+        shadowed_source_words: &HashSet<String>,
+    ) -> ZhConverter {
+        let mut mapping: HashMap<&str, &str> = HashMap::with_capacity(
+            self.source_words.len() + shadowing_pairs.len(),
+        );
+        for (from, to) in self.source_words.iter().zip(self.target_words.iter()) {
+            if !shadowed_source_words.contains(from) {
+                mapping.insert(from, to);
+            }
+        }
+        for (from, to) in &shadowing_pairs {
+            // shadowing entries take precedence over base entries with the same source key
+            mapping.insert(from, to);
+        }
+        let mut source_words = Vec::with_capacity(mapping.len());
+        let mut target_words = Vec::with_capacity(mapping.len());
+        for (from, to) in mapping {
+            source_words.push(from.to_owned());
+            target_words.push(to.to_owned());
+        }
+        let automaton = CharwiseDoubleArrayAhoCorasickBuilder::new()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(source_words.iter())
+            .expect("merged shadowing rules already filtered for empty sources");
+        let max_pattern_len = source_words.iter().map(String::len).max().unwrap_or(0);
+        ZhConverter {
+            variant: self.variant,
+            automaton,
+            source_words,
+            max_pattern_len,
+            target_words,
+            // carry over the base converter's exclusion/protection config; the shadowing merge only
+            // touches the primary mapping, so these must survive the wikitext fast path
+            exclusion_automaton: self.exclusion_automaton.as_ref().map(clone_automaton),
+            protection: self.protection.as_ref().map(|p| Protection {
+                regexes: p.regexes.clone(),
+                protect_latin: p.protect_latin,
+            }),
+        }
+    }
+
     /// Convert the given text, parsing and applying adhoc Mediawiki conversion rules in it.
     ///
     /// Basic MediaWiki conversion rules like `-{FOOBAR}-` or `-{zh-hant:FOO;zh-hans:BAR}-` are
@@ -254,24 +745,35 @@ impl ZhConverter {
                     ),
                 }
             }
-            if !shadowing_pairs.is_empty() {
-                let mut shadowing_target_words = Vec::with_capacity(shadowing_pairs.len());
-                let shadowing_automaton = CharwiseDoubleArrayAhoCorasickBuilder::new()
-                    .match_kind(MatchKind::LeftmostLongest)
-                    .build::<_, _, u32>(shadowing_pairs.into_iter().map(|(f, t)| {
-                        shadowing_target_words.push(t);
-                        f
-                    }))
-                    .expect("Rules feed to temporay DAAC already filtered");
-                convert_to = Box::new(move |text: &str, output: &mut String| {
-                    self.convert_to_with(
-                        text,
-                        output,
-                        &shadowing_automaton,
-                        shadowing_target_words.as_slice(),
-                        &shadowed_source_words,
-                    )
-                })
+            if !shadowing_pairs.is_empty() || !shadowed_source_words.is_empty() {
+                if self.can_merge_shadowing() {
+                    // Fast path: build the merged automaton once; a single O(n) pass handles
+                    // shadowing while preserving the full base table.
+                    let merged = self.with_shadowing(shadowing_pairs, &shadowed_source_words);
+                    convert_to = Box::new(move |text: &str, output: &mut String| {
+                        merged.convert_to(text, output)
+                    })
+                } else if !shadowing_pairs.is_empty() {
+                    // Fallback for converters without retained `source_words`: keep the base
+                    // automaton and consult a secondary shadowing automaton per match.
+                    let mut shadowing_target_words = Vec::with_capacity(shadowing_pairs.len());
+                    let shadowing_automaton = CharwiseDoubleArrayAhoCorasickBuilder::new()
+                        .match_kind(MatchKind::LeftmostLongest)
+                        .build::<_, _, u32>(shadowing_pairs.into_iter().map(|(f, t)| {
+                            shadowing_target_words.push(t);
+                            f
+                        }))
+                        .expect("Rules fed to temporary DAAC already filtered");
+                    convert_to = Box::new(move |text: &str, output: &mut String| {
+                        self.convert_to_with(
+                            text,
+                            output,
+                            &shadowing_automaton,
+                            shadowing_target_words.as_slice(),
+                            &shadowed_source_words,
+                        )
+                    })
+                }
             }
         };
 
@@ -380,6 +882,142 @@ impl ZhConverter {
     //     }
     // }
 
+    /// Serialize this converter into a self-describing byte payload.
+    ///
+    /// [`ZhConverterBuilder::build`] rebuilds a `CharwiseDoubleArrayAhoCorasick` from scratch every
+    /// time, which is the dominant startup cost for large dictionaries. Serializing once and loading
+    /// via [`from_bytes`](Self::from_bytes) avoids it.
+    ///
+    /// The framed layout, inspired by the Hadoop SequenceFile format, is:
+    ///
+    /// ```text
+    /// magic "ZHCV" | version (u8) | codec (u8) | variant (u8-len-prefixed UTF-8) | payload
+    /// ```
+    ///
+    /// where `payload` is the length-prefixed automaton blob followed by the `target_words` section
+    /// (a count then varint-length-prefixed UTF-8 strings), the `source_words` section in the same
+    /// encoding and a varint `max_pattern_len`, optionally deflate-compressed per the `codec` flag
+    /// (`0` = none, `1` = deflate).
+    pub fn serialize_to_vec(&self, compress: bool) -> Vec<u8> {
+        let mut payload = Vec::new();
+        let automaton = self.automaton.serialize();
+        payload.extend_from_slice(&(automaton.len() as u64).to_le_bytes());
+        payload.extend_from_slice(&automaton);
+        write_varint(&mut payload, self.target_words.len() as u64);
+        for word in &self.target_words {
+            write_varint(&mut payload, word.len() as u64);
+            payload.extend_from_slice(word.as_bytes());
+        }
+        // persist source_words and max_pattern_len so a loaded converter is a full drop-in (needed
+        // by wikitext global-rule shadowing and by convert_stream)
+        write_varint(&mut payload, self.source_words.len() as u64);
+        for word in &self.source_words {
+            write_varint(&mut payload, word.len() as u64);
+            payload.extend_from_slice(word.as_bytes());
+        }
+        write_varint(&mut payload, self.max_pattern_len as u64);
+
+        let (codec, payload) = if compress {
+            (CODEC_DEFLATE, deflate_compress(&payload))
+        } else {
+            (CODEC_NONE, payload)
+        };
+
+        let variant = self.variant.to_string();
+        let mut out = Vec::with_capacity(SERIAL_MAGIC.len() + 3 + variant.len() + payload.len());
+        out.extend_from_slice(SERIAL_MAGIC);
+        out.push(SERIAL_VERSION);
+        out.push(codec);
+        out.push(variant.len() as u8);
+        out.extend_from_slice(variant.as_bytes());
+        out.extend_from_slice(&payload);
+        out
+    }
+
+    /// Load a converter previously produced by [`serialize_to_vec`](Self::serialize_to_vec).
+    ///
+    /// The magic bytes and format version are validated, and the stored variant tag must match
+    /// `target`; a mismatch is rejected rather than producing a silently-wrong converter.
+    ///
+    /// # Safety
+    /// The framing header is validated, but the embedded automaton section is handed to
+    /// [`CharwiseDoubleArrayAhoCorasick::deserialize_unchecked`], which performs no validation and
+    /// is undefined behavior on a corrupt or hostile blob. The caller must guarantee `bytes` was
+    /// produced by [`serialize_to_vec`](Self::serialize_to_vec) (or `build.rs`'s matching codegen)
+    /// and has not been tampered with — e.g. a build-time `include_bytes!` blob.
+    pub unsafe fn from_bytes(target: Variant, bytes: &[u8]) -> io::Result<ZhConverter> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_owned());
+        let mut cur = bytes;
+        if cur.len() < SERIAL_MAGIC.len() + 2 || &cur[..SERIAL_MAGIC.len()] != SERIAL_MAGIC {
+            return Err(invalid("bad magic"));
+        }
+        cur = &cur[SERIAL_MAGIC.len()..];
+        let version = cur[0];
+        if version != SERIAL_VERSION {
+            return Err(invalid("unsupported format version"));
+        }
+        let codec = cur[1];
+        cur = &cur[2..];
+        let vlen = *cur.first().ok_or_else(|| invalid("truncated header"))? as usize;
+        cur = &cur[1..];
+        if cur.len() < vlen {
+            return Err(invalid("truncated variant tag"));
+        }
+        let variant_str =
+            std::str::from_utf8(&cur[..vlen]).map_err(|_| invalid("bad variant tag"))?;
+        let variant = Variant::from_str(variant_str).map_err(|_| invalid("unknown variant tag"))?;
+        if variant != target {
+            return Err(invalid("variant mismatch"));
+        }
+        cur = &cur[vlen..];
+
+        let payload = match codec {
+            CODEC_NONE => cur.to_vec(),
+            CODEC_DEFLATE => deflate_decompress(cur),
+            _ => return Err(invalid("unknown codec")),
+        };
+
+        let mut p = payload.as_slice();
+        let alen = read_u64(&mut p).ok_or_else(|| invalid("truncated automaton length"))? as usize;
+        if p.len() < alen {
+            return Err(invalid("truncated automaton blob"));
+        }
+        // SAFETY: upheld by the caller's `from_bytes` contract — `bytes` is a trusted blob.
+        let (automaton, _) =
+            unsafe { CharwiseDoubleArrayAhoCorasick::<u32>::deserialize_unchecked(&p[..alen]) };
+        p = &p[alen..];
+
+        let read_words = |p: &mut &[u8]| -> io::Result<Vec<String>> {
+            let count = read_varint(p).ok_or_else(|| invalid("truncated word count"))? as usize;
+            let mut words = Vec::with_capacity(count);
+            for _ in 0..count {
+                let len = read_varint(p).ok_or_else(|| invalid("truncated word length"))? as usize;
+                if p.len() < len {
+                    return Err(invalid("truncated word"));
+                }
+                let word = std::str::from_utf8(&p[..len]).map_err(|_| invalid("bad word encoding"))?;
+                words.push(word.to_owned());
+                *p = &p[len..];
+            }
+            Ok(words)
+        };
+
+        let target_words = read_words(&mut p)?;
+        let source_words = read_words(&mut p)?;
+        let max_pattern_len =
+            read_varint(&mut p).ok_or_else(|| invalid("truncated max_pattern_len"))? as usize;
+
+        Ok(ZhConverter {
+            variant,
+            automaton,
+            source_words,
+            max_pattern_len,
+            target_words,
+            exclusion_automaton: None,
+            protection: None,
+        })
+    }
+
     /// Count the sum of lengths of matched source words to be substituted in the given text.
     pub fn count_matched(&self, text: &str) -> usize {
         self.automaton
@@ -389,6 +1027,45 @@ impl ZhConverter {
     }
 }
 
+/// One layer of conversion rules in a layered composition.
+///
+/// A layer carries its own additions and removals. When folded by
+/// [`ZhConverterBuilder::with_layer`], a later layer's additions override earlier layers' entries
+/// with the same `from` key, and its removals delete keys contributed by any lower layer.
+#[derive(Debug, Clone, Default)]
+pub struct RuleLayer {
+    adds: Vec<(String, String)>,
+    removes: Vec<String>,
+}
+
+impl RuleLayer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Build a layer from a list of `from -> to` pairs.
+    pub fn from_pairs(pairs: &[(impl AsRef<str>, impl AsRef<str>)]) -> Self {
+        let mut layer = Self::new();
+        for (from, to) in pairs {
+            layer = layer.add(from, to);
+        }
+        layer
+    }
+
+    /// Add a `from -> to` rule to this layer.
+    pub fn add(mut self, from: impl AsRef<str>, to: impl AsRef<str>) -> Self {
+        self.adds
+            .push((from.as_ref().to_owned(), to.as_ref().to_owned()));
+        self
+    }
+
+    /// Mask out any rule with the given `from` key contributed by a lower layer.
+    pub fn remove(mut self, from: impl AsRef<str>) -> Self {
+        self.removes.push(from.as_ref().to_owned());
+        self
+    }
+}
+
 /// A builder that helps build a `ZhConverter`.
 ///
 /// # Example
@@ -416,6 +1093,18 @@ pub struct ZhConverterBuilder<'t> {
     adds: HashMap<String, String>,
     /// Rules to be removed, from page rules or cgroups
     removes: HashMap<String, String>, // TODO: unnecessary owned type
+    /// Context phrases inside which the default conversion of a matched segment is suppressed
+    exclusions: HashSet<String>,
+    /// User regexes whose matches are protected from conversion
+    protect_regexes: Vec<Regex>,
+    /// Whether to auto-protect contiguous ASCII/Latin runs
+    protect_latin: bool,
+    /// Width normalization: `Some(true)` maps fullwidth to halfwidth, `Some(false)` the reverse
+    width: Option<bool>,
+    /// Ordered stack of rule layers, folded later-wins at build time
+    layers: Vec<RuleLayer>,
+    /// Accumulated OpenCC dictionary table, kept in the pipe format used by [`merge_tables`]
+    opencc: (String, String),
 }
 
 impl<'t> ZhConverterBuilder<'t> {
@@ -511,6 +1200,15 @@ impl<'t> ZhConverterBuilder<'t> {
         self
     }
 
+    /// Add a batch of `from -> to` pairs, e.g. those produced by
+    /// [`MediaWikiImporter::build`](crate::importer::MediaWikiImporter::build). Entries with an
+    /// empty `from` are skipped. They share the same precedence as [`add_conv_pair`](#method.add_conv_pair).
+    pub fn add_pairs(mut self, pairs: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.adds
+            .extend(pairs.into_iter().filter(|(from, _to)| !from.is_empty()));
+        self
+    }
+
     /// Mark a single conversion pair as removed.
     ///
     /// Any rule with the same `from`, whether specified via `add_conv_pair`, `conv_lines` or `table`, is removed.
@@ -520,6 +1218,139 @@ impl<'t> ZhConverterBuilder<'t> {
         self
     }
 
+    /// Add an ordered list of tables merged with explicit priority.
+    ///
+    /// Uses [`merge_tables_many`](crate::tables::merge_tables_many): later tables override earlier
+    /// ones on identical source phrases, so callers can compose `base → region → user CGroups`
+    /// deterministically. The merged pairs are folded into the same high-precedence aggregation as
+    /// [`add_conv_pair`](#method.add_conv_pair).
+    pub fn merge_tables(mut self, tables: &[Table]) -> Self {
+        let (froms, tos) = crate::tables::merge_tables_many(tables);
+        self.adds.extend(
+            froms
+                .split('|')
+                .zip(tos.split('|'))
+                .filter(|(from, _to)| !from.is_empty())
+                .map(|(from, to)| (from.to_owned(), to.to_owned())),
+        );
+        self
+    }
+
+    /// Push a rule layer onto the ordered composition stack.
+    ///
+    /// Layers let callers stack, say, a `zh-Hant` base, a `zh-TW` locale table and a project
+    /// glossary without pre-merging the maps themselves. Later layers take precedence: a higher
+    /// layer's addition wins on a conflicting `from` key, and its removal deletes a key contributed
+    /// by any lower layer. Layers are applied after tables and `add_conv_pair`/`conv_lines` rules,
+    /// so they have the final say; the subsequent automaton build is unchanged.
+    pub fn with_layer(mut self, layer: RuleLayer) -> Self {
+        self.layers.push(layer);
+        self
+    }
+
+    /// Enable width normalization: map fullwidth ASCII, digits, punctuation and the ideographic
+    /// space to their halfwidth equivalents (`to_halfwidth = true`), or the reverse.
+    ///
+    /// Chinese text pasted from IMEs and CJK editors is often riddled with fullwidth Latin and
+    /// punctuation; this folds them into plain ASCII. The rules are single-char→single-char and
+    /// feed through the same automaton as any other table — see
+    /// [`width_pairs`](crate::tables::width_pairs).
+    pub fn normalize_width(mut self, to_halfwidth: bool) -> Self {
+        self.width = Some(to_halfwidth);
+        self
+    }
+
+    /// Register a regex whose matches are copied verbatim, shielding e.g. URLs, code spans,
+    /// filenames or English words from conversion.
+    ///
+    /// Protected regions are resolved on each `convert` call: their union is computed first, then
+    /// the automaton runs only over the gaps between them. See also [`protect_latin`](#method.protect_latin).
+    pub fn protect(mut self, regex: Regex) -> Self {
+        self.protect_regexes.push(regex);
+        self
+    }
+
+    /// Set whether to auto-protect contiguous ASCII/Latin runs (off by default).
+    ///
+    /// When enabled, maximal runs of ASCII graphic characters are treated as protected regions,
+    /// which keeps mixed-language technical text (identifiers, URLs, paths) intact.
+    pub fn protect_latin(mut self, enabled: bool) -> Self {
+        self.protect_latin = enabled;
+        self
+    }
+
+    /// Load an external dictionary in the OpenCC line format and merge it into the conversion table.
+    ///
+    /// Each non-blank, non-comment line has the shape `FROM\tTO1 TO2 TO3`, where the space-separated
+    /// candidates after the tab are ranked with the first one preferred. Only the first candidate is
+    /// kept for the deterministic substitution. The pairs are kept apart from the `adds` aggregation:
+    /// at build time they override the base tables but are themselves overridden by
+    /// [`add_conv_pair`](#method.add_conv_pair) / [`conv_lines`](#method.conv_lines) rules. Comment
+    /// lines start with `#`.
+    ///
+    /// This gives the crate parity with the OpenCC dictionary ecosystem (STPhrases, TSPhrases, …)
+    /// without recompiling. The parsed pairs are folded into an accumulating table via
+    /// [`merge_tables`](crate::tables::merge_tables) so longest-source-first priority is preserved,
+    /// and the merged table flows into the builder aggregation in [`build`](#method.build).
+    pub fn add_opencc_dict<R: BufRead>(mut self, reader: R) -> io::Result<Self> {
+        let mut pairs: Vec<(String, String)> = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (from, rest) = match line.split_once('\t') {
+                Some(parts) => parts,
+                None => continue,
+            };
+            if from.is_empty() {
+                continue;
+            }
+            // candidates are ranked; the first one is preferred
+            let to = match rest.split_whitespace().next() {
+                Some(to) => to,
+                None => continue,
+            };
+            pairs.push((from.to_owned(), to.to_owned()));
+        }
+        // emit longest-source-first so `merge_tables`' length-ordered merge preserves priority
+        pairs.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
+        let froms = pairs.iter().map(|(f, _)| f.as_str()).collect::<Vec<_>>().join("|");
+        let tos = pairs.iter().map(|(_, t)| t.as_str()).collect::<Vec<_>>().join("|");
+        self.opencc = if self.opencc.0.is_empty() {
+            (froms, tos)
+        } else {
+            crate::tables::merge_tables(
+                (self.opencc.0.as_str(), self.opencc.1.as_str()),
+                (&froms, &tos),
+            )
+        };
+        Ok(self)
+    }
+
+    /// Add an exclusion entry: `(segment, context)` meaning "do not apply the default conversion of
+    /// `segment` when it occurs inside `context`".
+    ///
+    /// Only `context` is retained — at conversion time the whole context span is protected, so any
+    /// primary match falling inside it is left untouched. The `segment` argument documents intent
+    /// and mirrors the shape of the upstream exclusion tables. Empty contexts are ignored.
+    pub fn add_exclusion(mut self, _segment: impl AsRef<str>, context: impl AsRef<str>) -> Self {
+        let context = context.as_ref();
+        if !context.is_empty() {
+            self.exclusions.insert(context.to_owned());
+        }
+        self
+    }
+
+    /// Add a batch of `(segment, context)` exclusion entries, e.g. [`ZH_HANS_EXCLUSION_SEED`].
+    pub fn exclusions(mut self, entries: &[(impl AsRef<str>, impl AsRef<str>)]) -> Self {
+        for (segment, context) in entries {
+            self = self.add_exclusion(segment, context);
+        }
+        self
+    }
+
     /// Add a text of conv lines.
     ///
     /// e.g.
@@ -564,6 +1395,12 @@ impl<'t> ZhConverterBuilder<'t> {
             tables,
             adds,
             removes,
+            exclusions,
+            protect_regexes,
+            protect_latin,
+            width,
+            layers,
+            opencc,
         } = self;
         // let v = lz4_flex::compress_prepend_size(b"hello")
         // dbg!(v.len());
@@ -579,20 +1416,71 @@ impl<'t> ZhConverterBuilder<'t> {
                 .filter(|(from, to)| !(from.is_empty() && to.is_empty())) // empty str would trouble AC
                 .filter(|(from, _to)| !removes.contains_key(from)),
         );
+        // OpenCC dictionaries sit above base tables but below explicit adds
+        mapping.extend(
+            opencc
+                .0
+                .split('|')
+                .zip(opencc.1.split('|'))
+                .filter(|(from, to)| !(from.is_empty() && to.is_empty()))
+                .filter(|(from, _to)| !removes.contains_key(*from))
+                .map(|(from, to)| (from.to_owned(), to.to_owned())),
+        );
         mapping.extend(
             adds.iter()
                 .filter(|(from, _to)| !removes.contains_key(from.as_str()))
                 .map(|(from, to)| (from.to_owned(), to.to_owned())),
         );
-        let sequence = mapping.keys();
+        if let Some(to_halfwidth) = *width {
+            mapping.extend(
+                crate::tables::width_pairs(to_halfwidth)
+                    .into_iter()
+                    .filter(|(from, _to)| !removes.contains_key(from.as_str())),
+            );
+        }
+        // Fold the rule layers in order: later layers override earlier keys, removals mask them out.
+        for layer in layers {
+            for from in &layer.removes {
+                mapping.remove(from);
+            }
+            for (from, to) in &layer.adds {
+                if !from.is_empty() {
+                    mapping.insert(from.to_owned(), to.to_owned());
+                }
+            }
+        }
+
+        // Split the mapping into index-aligned source/target vectors in a single pass. The value a
+        // match reports is the pattern's feed order, so `source_words` and `target_words` share it.
+        let mut source_words = Vec::with_capacity(mapping.len());
+        let mut target_words = Vec::with_capacity(mapping.len());
+        for (from, to) in mapping {
+            source_words.push(from);
+            target_words.push(to);
+        }
         let automaton = CharwiseDoubleArrayAhoCorasickBuilder::new()
             .match_kind(MatchKind::LeftmostLongest)
-            .build(sequence)
+            .build(source_words.iter())
             .unwrap();
+        let exclusion_automaton = (!exclusions.is_empty()).then(|| {
+            CharwiseDoubleArrayAhoCorasickBuilder::new()
+                .match_kind(MatchKind::LeftmostLongest)
+                .build(exclusions.iter())
+                .unwrap()
+        });
+        let protection = (!protect_regexes.is_empty() || *protect_latin).then(|| Protection {
+            regexes: protect_regexes.clone(),
+            protect_latin: *protect_latin,
+        });
+        let max_pattern_len = source_words.iter().map(String::len).max().unwrap_or(0);
         ZhConverter {
             variant: *target,
             automaton,
-            target_words: mapping.into_values().collect(),
+            source_words,
+            max_pattern_len,
+            target_words,
+            exclusion_automaton,
+            protection,
         }
     }
 }
@@ -0,0 +1,36 @@
+//! Runtime loading of precompiled converters embedded at build time.
+//!
+//! Enabled by the `embed-dicts` feature. The blobs are produced by `build.rs` (see its module docs)
+//! and baked into the binary as `&'static [u8]`. [`load`] decodes one via
+//! [`ZhConverter::from_bytes`], skipping the automaton build entirely.
+//! [`get_builtin_converter`](crate::get_builtin_converter) does not call [`load`]; it builds its
+//! converters at runtime.
+
+use crate::{ZhConverter, Variant};
+
+macro_rules! embedded {
+    ($variant:expr, $name:literal) => {
+        (
+            $variant,
+            include_bytes!(concat!(env!("OUT_DIR"), "/embedded/", $name, ".bin")) as &'static [u8],
+        )
+    };
+}
+
+/// The embedded blobs, one per bundled variant, keyed by variant tag.
+static BLOBS: &[(Variant, &'static [u8])] = &[
+    embedded!(Variant::ZhHant, "zh-Hant"),
+    embedded!(Variant::ZhHans, "zh-Hans"),
+    embedded!(Variant::ZhTW, "zh-TW"),
+    embedded!(Variant::ZhHK, "zh-HK"),
+    embedded!(Variant::ZhCN, "zh-CN"),
+];
+
+/// Load the embedded converter for `variant`, or `None` if no blob is bundled for it.
+pub fn load(variant: Variant) -> Option<ZhConverter> {
+    BLOBS.iter().find(|(v, _)| *v == variant).map(|(_, bytes)| {
+        // SAFETY: the blob is baked in at build time by `build.rs` from a freshly serialized
+        // automaton, so it always satisfies `from_bytes`'s trusted-input contract.
+        unsafe { ZhConverter::from_bytes(variant, bytes) }.expect("embedded blob is valid")
+    })
+}
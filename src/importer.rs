@@ -0,0 +1,135 @@
+//! Ingest upstream MediaWiki conversion data at runtime (or via a build script) and turn it into
+//! `(from, to)` pairs consumable by [`ZhConverterBuilder`](crate::ZhConverterBuilder). This lets
+//! users refresh or customize the built-in tables from authoritative sources instead of waiting for
+//! crate releases.
+//!
+//! The [`MediaWikiImporter`] mirrors the three-layer pipeline of the upstream zhtable Makefile: an
+//! auto-generated base table, a manual additions table, and a manual exclusion list that drops
+//! specific source phrases before emission. Keys are deduplicated per layer with last-writer-wins,
+//! entries with an empty `from` are rejected (they would break the Aho-Corasick build), and
+//! [`MediaWikiImporter::build`] returns the combined pairs in longest-source-first order.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::utils::regex;
+
+/// Parse the `$zh2Hant = array('一吊錢' => '一吊錢', ...)` PHP-array form used by `ZhConversion.php`.
+///
+/// Only the `'from' => 'to'` entries are extracted; surrounding PHP syntax is ignored. Escaped
+/// quotes (`\'`) and backslashes (`\\`) inside the single-quoted literals are unescaped.
+pub fn parse_php_array(src: &str) -> Vec<(String, String)> {
+    let re = regex!(r"'((?:[^'\\]|\\.)*)'\s*=>\s*'((?:[^'\\]|\\.)*)'");
+    re.captures_iter(src)
+        .map(|cap| (unescape_php(&cap[1]), unescape_php(&cap[2])))
+        .collect()
+}
+
+/// Parse a line-oriented phrase source, one `FROM<TAB>TO` entry per line.
+///
+/// Blank lines and lines starting with `#` are skipped. A line with no tab is treated as an
+/// identity mapping (`FROM => FROM`), matching how manual phrase lists are sometimes authored.
+pub fn parse_phrase_lines(src: &str) -> Vec<(String, String)> {
+    src.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split_once('\t') {
+            Some((from, to)) => (from.to_owned(), to.to_owned()),
+            None => (line.to_owned(), line.to_owned()),
+        })
+        .collect()
+}
+
+fn unescape_php(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// A three-layer importer producing merged `(from, to)` pairs.
+#[derive(Debug, Default)]
+pub struct MediaWikiImporter {
+    /// Auto-generated base table.
+    base: HashMap<String, String>,
+    /// Manual additions, overriding the base layer.
+    adds: HashMap<String, String>,
+    /// Manual exclusion list: source phrases dropped before emission.
+    removes: HashSet<String>,
+}
+
+impl MediaWikiImporter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Feed the base layer with entries parsed from a `ZhConversion.php` array.
+    pub fn base_php_array(mut self, src: &str) -> Self {
+        extend_layer(&mut self.base, parse_php_array(src));
+        self
+    }
+
+    /// Feed the base layer with entries from a line-oriented phrase source.
+    pub fn base_lines(mut self, src: &str) -> Self {
+        extend_layer(&mut self.base, parse_phrase_lines(src));
+        self
+    }
+
+    /// Feed the manual additions layer, which overrides the base layer on conflicting keys.
+    pub fn add_lines(mut self, src: &str) -> Self {
+        extend_layer(&mut self.adds, parse_phrase_lines(src));
+        self
+    }
+
+    /// Feed the manual exclusion list: the given source phrases are dropped before emission.
+    pub fn exclude_lines(mut self, src: &str) -> Self {
+        self.removes.extend(
+            parse_phrase_lines(src)
+                .into_iter()
+                .map(|(from, _to)| from),
+        );
+        self
+    }
+
+    /// Mark a single source phrase as excluded.
+    pub fn exclude(mut self, from: impl Into<String>) -> Self {
+        self.removes.insert(from.into());
+        self
+    }
+
+    /// Build the merged table.
+    ///
+    /// Layers are folded base → adds (last-writer-wins per key), then the exclusion list and any
+    /// empty-`from` entries are dropped. The result is emitted in longest-source-first order so the
+    /// automaton keeps leftmost-longest semantics.
+    pub fn build(self) -> Vec<(String, String)> {
+        let Self {
+            mut base,
+            adds,
+            removes,
+        } = self;
+        for (from, to) in adds {
+            base.insert(from, to);
+        }
+        let mut pairs: Vec<(String, String)> = base
+            .into_iter()
+            .filter(|(from, _to)| !from.is_empty() && !removes.contains(from))
+            .collect();
+        pairs.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(&b.0)));
+        pairs
+    }
+}
+
+/// Extend a layer with `pairs`, applying last-writer-wins on duplicate keys.
+fn extend_layer(layer: &mut HashMap<String, String>, pairs: Vec<(String, String)>) {
+    for (from, to) in pairs {
+        layer.insert(from, to);
+    }
+}
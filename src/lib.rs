@@ -50,6 +50,9 @@ mod converter;
 mod utils;
 
 pub mod converters;
+#[cfg(feature = "embed-dicts")]
+pub mod embedded;
+pub mod importer;
 pub mod tables;
 
 pub mod pagerules;
@@ -107,18 +110,82 @@ pub fn is_hans(text: &str) -> bool {
 /// confidence level. A value close to 1 indicate high confidence. A value close to 0
 /// indicates low confidence. `0.5` indicates undeterminable (half-half).
 pub fn is_hans_probability(text: &str) -> f32 {
-    let non_hant_score = ZH_TO_HANT_CONVERTER.count_matched(text) as f32;
-    let non_hans_score = ZH_TO_HANS_CONVERTER.count_matched(text) as f32;
-    // let mut ratio = if non_hans_score == 0 {
-    //     f32::MAX
-    // } else {
-    //     non_hant_score as f32 / non_hans_score as f32
-    // } - 1.0;
-    // if ratio < 0.0 {
-    //     ratio = -(1.0 / (ratio + 1.0) - 1.0);
-    // }
-    // 1f32 / (1f32 + E.powf(-ratio))
-    non_hant_score / (non_hans_score + non_hant_score)
+    // Renormalize the `ZhHans` / `ZhHant` scores from the shared distribution over just the two.
+    let scores = variant_scores(text);
+    let hans = variant_score(&scores, Variant::ZhHans);
+    let hant = variant_score(&scores, Variant::ZhHant);
+    let total = hans + hant;
+    if total == 0.0 {
+        0.5
+    } else {
+        hans / total
+    }
+}
+
+/// The candidate variants scored by [`variant_scores`], in a fixed order.
+const SCORED_VARIANTS: [Variant; 5] = [
+    Variant::ZhCN,
+    Variant::ZhTW,
+    Variant::ZhHK,
+    Variant::ZhHans,
+    Variant::ZhHant,
+];
+
+/// Count the number of Han (CJK) codepoints in `text`.
+///
+/// Used to length-normalize matched-rule counts so that CJK character width does not skew scores.
+fn count_han(text: &str) -> usize {
+    text.chars()
+        .filter(|&c| {
+            matches!(c as u32,
+                0x3400..=0x4DBF       // CJK Extension A
+                | 0x4E00..=0x9FFF     // CJK Unified Ideographs
+                | 0xF900..=0xFAFF     // CJK Compatibility Ideographs
+                | 0x20000..=0x2A6DF   // CJK Extension B
+                | 0x2A700..=0x2EBEF)  // CJK Extension C–F
+        })
+        .count()
+}
+
+/// Score each candidate variant as a probability that the input text is already written in it.
+///
+/// For every candidate converter the summed byte length of matched source words (as reported by
+/// `count_matched`) is normalized by the number of Han codepoints in the input — a variant's
+/// "foreignness" (how much conversion the text would still need). These foreignness scores are turned into a probability distribution via softmax over
+/// their negations, so a lower foreignness yields a higher probability. The returned confidences
+/// always sum to `1.0`; empty input or input with no Han characters yields a uniform distribution.
+pub fn variant_scores(text: &str) -> Vec<(Variant, f32)> {
+    let han = count_han(text);
+    if han == 0 {
+        let uniform = 1.0 / SCORED_VARIANTS.len() as f32;
+        return SCORED_VARIANTS.iter().map(|&v| (v, uniform)).collect();
+    }
+    let han = han as f32;
+    let foreignness = [
+        ZH_TO_CN_CONVERTER.count_matched(text) as f32 / han,
+        ZH_TO_TW_CONVERTER.count_matched(text) as f32 / han,
+        ZH_TO_HK_CONVERTER.count_matched(text) as f32 / han,
+        ZH_TO_HANS_CONVERTER.count_matched(text) as f32 / han,
+        ZH_TO_HANT_CONVERTER.count_matched(text) as f32 / han,
+    ];
+    // softmax over the negated foreignness scores; subtract the max for numerical stability
+    let max = foreignness.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+    let exps: Vec<f32> = foreignness.iter().map(|&f| (max - f).exp()).collect();
+    let sum: f32 = exps.iter().sum();
+    SCORED_VARIANTS
+        .iter()
+        .zip(exps)
+        .map(|(&v, e)| (v, e / sum))
+        .collect()
+}
+
+/// Look up the score of a single variant in the result of [`variant_scores`].
+fn variant_score(scores: &[(Variant, f32)], variant: Variant) -> f32 {
+    scores
+        .iter()
+        .find(|(v, _)| *v == variant)
+        .map(|(_, s)| *s)
+        .unwrap_or(0.0)
 }
 
 /// Determine the Chinese variant of the input text.
@@ -126,70 +193,37 @@ pub fn is_hans_probability(text: &str) -> f32 {
 /// # Returns
 /// Possible return values are only `ZhCN`, `ZhTW` and `ZhHK`.
 pub fn infer_variant(text: &str) -> Variant {
-    let non_cn_score = ZH_TO_CN_CONVERTER.count_matched(text);
-    let non_tw_score = ZH_TO_TW_CONVERTER.count_matched(text);
-    let non_hk_score = ZH_TO_HK_CONVERTER.count_matched(text);
-
-    // authored by ChatGPT
-    if non_cn_score <= non_tw_score && non_cn_score <= non_hk_score {
-        Variant::ZhCN
-    } else if non_tw_score <= non_cn_score && non_tw_score <= non_hk_score {
-        Variant::ZhTW
-    } else {
-        Variant::ZhHK
-    }
+    let scores = variant_scores(text);
+    // Keep the first candidate on ties (strict `>`), so ambiguous/empty input prefers `ZhCN` as the
+    // original threshold comparison did, rather than the last-wins behavior of `max_by`.
+    [Variant::ZhCN, Variant::ZhTW, Variant::ZhHK]
+        .into_iter()
+        .reduce(|best, v| {
+            if variant_score(&scores, v) > variant_score(&scores, best) {
+                v
+            } else {
+                best
+            }
+        })
+        .unwrap()
 }
 
 /// Determine the Chinese variant of the input text with confidence.
 ///
 /// # Returns
-/// A array of `(variant, confidence_level)`, where `confidence_level` is in the range `[0, 1]
-/// (inclusive).
+/// A array of `(variant, confidence_level)`, where `confidence_level` is in the range `[0, 1]`
+/// (inclusive) and the confidences sum to `1.0`, sorted by descending confidence.
 // /// Note that, unlike [`is_hans_confidence`](is_hans_confidence), a `confidence_level` greater
 // /// than `0.5` might not imply high enough likelihood.
 pub fn infer_variant_confidence(text: &str) -> [(Variant, f32); 5] {
-    // let total = text.len() as f32;
-    let non_cn_score = ZH_TO_CN_CONVERTER.count_matched(text) as f32;
-    let non_tw_score = ZH_TO_TW_CONVERTER.count_matched(text) as f32;
-    let non_hk_score = ZH_TO_HK_CONVERTER.count_matched(text) as f32;
-    let non_hant_score = ZH_TO_HANT_CONVERTER.count_matched(text) as f32;
-    let non_hans_score = ZH_TO_HANS_CONVERTER.count_matched(text) as f32;
-
-    let total_score = non_cn_score + non_tw_score + non_hk_score - non_hant_score;
-    // let region_total = non_cn_score + non_tw_score + non_hk_score - non_hant_score;
-    // let script_total = non_hant_score + non_hans_score;
+    let scores = variant_scores(text);
     let mut confidence_map = [
-        (
-            Variant::ZhCN,
-            1f32 - non_cn_score.min(total_score) / total_score,
-        ),
-        (
-            Variant::ZhTW,
-            1f32 - non_tw_score.min(total_score) / total_score,
-        ),
-        (
-            Variant::ZhHK,
-            1f32 - non_hk_score.min(total_score) / total_score,
-        ),
-        (
-            Variant::ZhHans,
-            1f32 - non_hans_score.min(total_score) / total_score,
-        ),
-        (
-            Variant::ZhHant,
-            1f32 - non_hant_score.min(total_score) / total_score,
-        ),
+        (Variant::ZhCN, variant_score(&scores, Variant::ZhCN)),
+        (Variant::ZhTW, variant_score(&scores, Variant::ZhTW)),
+        (Variant::ZhHK, variant_score(&scores, Variant::ZhHK)),
+        (Variant::ZhHans, variant_score(&scores, Variant::ZhHans)),
+        (Variant::ZhHant, variant_score(&scores, Variant::ZhHant)),
     ];
-    // let mut confidence_map = [(Variant::ZhCN, 1f32 - non_cn_score / region_total),(Variant::ZhTW, 1f32 - non_tw_score / region_total),(Variant::ZhHK, 1f32 - non_hk_score / region_total),(Variant::ZhHans,1f32 - non_hans_score / script_total),(Variant::ZhHant, 1f32 - non_hant_score / script_total)];
-    // let mut confidence_map = [(Variant::ZhCN, non_cn_score),(Variant::ZhTW, non_tw_score),(Variant::ZhHK, non_hk_score),(Variant::ZhHans,non_hans_score),(Variant::ZhHant, non_hant_score), (Variant::Zh, total)];
-
-    // let mut confidence_map = [
-    //     (Variant::ZhCN, 1f32 - non_cn_score / total),
-    //     (Variant::ZhTW, 1f32 - non_tw_score / total),
-    //     (Variant::ZhHK, 1f32 - non_hk_score / total),
-    //     (Variant::ZhHans, 1f32 - non_hans_score / total),
-    //     (Variant::ZhHant, 1f32 - non_hant_score / total),
-    // ];
     confidence_map.sort_by(|a, b| b.1.total_cmp(&a.1));
     confidence_map
 }
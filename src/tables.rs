@@ -91,6 +91,81 @@ pub fn merge_tables(conv1: (&str, &str), conv2: (&str, &str)) -> (String, String
     return (froms, tos);
 }
 
+/// Merge an ordered list of conversion tables into one, with explicit priority.
+///
+/// Later tables take precedence over earlier ones: on an identical source phrase, the target from
+/// the higher-priority (later) table wins. This lets callers stack `base → region → user CGroups`
+/// deterministically instead of chaining [`merge_tables`] two at a time. The combined table is
+/// emitted in longest-source-first order so the automaton keeps leftmost-longest semantics.
+pub fn merge_tables_many(tables: &[(&str, &str)]) -> (String, String) {
+    // later tables override earlier ones on identical source
+    let mut mapping: HashMap<&str, &str> = HashMap::new();
+    for (froms, tos) in tables {
+        for (from, to) in itertools::zip(froms.trim().split('|'), tos.trim().split('|')) {
+            mapping.insert(from, to);
+        }
+    }
+    // emit in longest-source-first order; break ties by source for determinism
+    let mut entries: Vec<(&str, &str)> = mapping.into_iter().collect();
+    entries.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(b.0)));
+    let mut froms = String::new();
+    let mut tos = String::new();
+    let mut it = entries.into_iter().peekable();
+    while let Some((from, to)) = it.next() {
+        froms.push_str(from);
+        tos.push_str(to);
+        if it.peek().is_some() {
+            froms.push('|');
+            tos.push('|');
+        }
+    }
+    (froms, tos)
+}
+
+/// Generate single-char conversion pairs mapping fullwidth ASCII/punctuation and the ideographic
+/// space to their halfwidth equivalents (`to_halfwidth = true`), or the reverse.
+///
+/// Covers U+FF01..=U+FF5E (offset 0xFEE0 from U+0021..=U+007E) and U+3000 → U+0020. Every codepoint
+/// is mapped individually so that case is preserved (e.g. `Ａ` and `ａ` map to `A` and `a`).
+pub fn width_pairs(to_halfwidth: bool) -> Vec<(String, String)> {
+    let mut pairs = Vec::with_capacity(0xFF5E - 0xFF01 + 2);
+    for code in 0xFF01u32..=0xFF5E {
+        let full = char::from_u32(code).unwrap();
+        let half = char::from_u32(code - 0xFEE0).unwrap();
+        if to_halfwidth {
+            pairs.push((full.to_string(), half.to_string()));
+        } else {
+            pairs.push((half.to_string(), full.to_string()));
+        }
+    }
+    // ideographic space U+3000 <-> U+0020
+    if to_halfwidth {
+        pairs.push(('\u{3000}'.to_string(), " ".to_string()));
+    } else {
+        pairs.push((" ".to_string(), '\u{3000}'.to_string()));
+    }
+    pairs
+}
+
+fn width_table_leaked(to_halfwidth: bool) -> (&'static str, &'static str) {
+    let pairs = width_pairs(to_halfwidth);
+    let froms = pairs.iter().map(|(f, _)| f.as_str()).collect::<Vec<_>>().join("|");
+    let tos = pairs.iter().map(|(_, t)| t.as_str()).collect::<Vec<_>>().join("|");
+    (
+        Box::leak(froms.into_boxed_str()),
+        Box::leak(tos.into_boxed_str()),
+    )
+}
+
+lazy_static! {
+    /// Table mapping fullwidth ASCII, digits and punctuation (and U+3000) to halfwidth.
+    pub static ref FULLWIDTH_TO_HALFWIDTH_TABLE: (&'static str, &'static str) =
+        width_table_leaked(true);
+    /// Table mapping halfwidth ASCII, digits and punctuation (and U+0020) to fullwidth.
+    pub static ref HALFWIDTH_TO_FULLWIDTH_TABLE: (&'static str, &'static str) =
+        width_table_leaked(false);
+}
+
 // pub const ZH_HANT_TO: &str = include_str!(concat!(env!("OUT_DIR"), "/zh2Hant.to.conv"));
 
 // pub const ZH_HANS_FROM: &str = include_str!(concat!(env!("OUT_DIR"), "/zh2Hans.from.conv"));